@@ -0,0 +1,601 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, DeriveInput, Lit, Meta, MetaList, NestedMeta, Path,
+};
+
+/// Create a kind method for struct
+/// # Examples
+/// ```
+/// use derive_error_kind::ErrorKind;
+///#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// enum ErrorType {
+///     A,
+///     B,
+///     C,
+/// }
+///
+/// #[derive(ErrorKind)]
+/// #[error_kind(ErrorType)]
+/// enum CacheError {
+///     #[error_kind(ErrorType, A)]
+///     Poisoned,
+///
+///     #[error_kind(ErrorType, B)]
+///     Missing,
+/// }
+///
+/// #[derive(ErrorKind)]
+/// #[error_kind(ErrorType)]
+/// enum ServiceError {
+///     #[error_kind(transparent)]
+///     Cache(CacheError),
+///
+///     #[error_kind(ErrorType, C)]
+///     Db,
+/// }
+///
+/// assert_eq!(ServiceError::Cache(CacheError::Missing).kind(), ErrorType::B);
+/// assert_eq!(ServiceError::Db.kind(), ErrorType::C);
+///
+/// // kind_chain() adds no entry for a transparent level, so it never duplicates the
+/// // inner kind: a single-level CacheError yields one entry, and delegating to it
+/// // through ServiceError::Cache doesn't add a second one.
+/// assert_eq!(ServiceError::Cache(CacheError::Missing).kind_chain(), vec![ErrorType::B]);
+/// assert_eq!(ServiceError::Db.kind_chain(), vec![ErrorType::C]);
+/// ```
+///
+/// `describe()` returns the explicit description literal when given one, and otherwise falls
+/// back to the stringified kind variant:
+/// ```
+/// use derive_error_kind::ErrorKind;
+/// #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// enum ErrorType {
+///     NotFound,
+/// }
+///
+/// #[derive(ErrorKind)]
+/// #[error_kind(ErrorType)]
+/// enum RepoError {
+///     #[error_kind(ErrorType, NotFound, "resource was not found")]
+///     Missing,
+///
+///     #[error_kind(ErrorType, NotFound)]
+///     Gone,
+/// }
+///
+/// assert_eq!(RepoError::Missing.describe(), "resource was not found");
+/// assert_eq!(RepoError::Gone.describe(), "NotFound");
+/// ```
+///
+/// `#[error_kind(transparent, from)]` additionally generates a `From<Inner>` impl for the
+/// variant, so inner errors can be propagated with `?` instead of wrapped by hand:
+/// ```
+/// use derive_error_kind::ErrorKind;
+/// #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// enum ErrorType {
+///     NotFound,
+///     InternalError,
+/// }
+///
+/// #[derive(Debug, ErrorKind)]
+/// #[error_kind(ErrorType)]
+/// enum CacheError {
+///     #[error_kind(ErrorType, NotFound)]
+///     Missing,
+/// }
+///
+/// #[derive(Debug, ErrorKind)]
+/// #[error_kind(ErrorType)]
+/// enum ServiceError {
+///     #[error_kind(transparent, from)]
+///     Cache(CacheError),
+///
+///     #[error_kind(ErrorType, InternalError)]
+///     Db,
+/// }
+///
+/// fn read_cache() -> Result<(), CacheError> {
+///     Err(CacheError::Missing)
+/// }
+///
+/// fn read_through() -> Result<(), ServiceError> {
+///     read_cache()?; // `?` converts CacheError into ServiceError via the generated From impl
+///     Ok(())
+/// }
+///
+/// assert_eq!(read_through().unwrap_err().kind(), ErrorType::NotFound);
+/// ```
+#[proc_macro_derive(ErrorKind, attributes(error_kind))]
+pub fn error_kind(input: TokenStream) -> TokenStream {
+    error_kind_macro(input)
+}
+
+fn error_kind_macro(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match input.data {
+        syn::Data::Enum(_) => derive_enum(input),
+        syn::Data::Struct(_) => derive_struct(input),
+        _ => {
+            let name = input.ident.clone();
+            let err = syn::Error::new_spanned(
+                &name,
+                "ErrorKind can only be derived for enums and structs",
+            );
+            err.to_compile_error().into()
+        }
+    }
+}
+
+fn derive_enum(input: DeriveInput) -> TokenStream {
+    let mut errors: Vec<syn::Error> = Vec::new();
+
+    let kind_ty = match get_kind_ty(&input) {
+        Ok(ty) => Some(ty),
+        Err(err) => {
+            errors.push(err);
+            None
+        }
+    };
+
+    let name = input.ident.clone();
+    let variants = match input.data {
+        syn::Data::Enum(data) => data.variants,
+        _ => unreachable!("derive_enum is only called for enum input"),
+    };
+
+    let mut kind_variants = Vec::new();
+    let mut from_variants = Vec::new();
+
+    for variant in variants.clone() {
+        let ident = variant.ident;
+        let fields = variant.fields;
+        let attr = match variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("error_kind"))
+        {
+            Some(attr) => attr,
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    &ident,
+                    "enum variants must have the attribute `error_kind`",
+                ));
+                continue;
+            }
+        };
+
+        let meta = match attr.parse_meta() {
+            Ok(syn::Meta::List(meta)) => meta,
+            _ => {
+                errors.push(syn::Error::new_spanned(attr, "error parsing meta"));
+                continue;
+            }
+        };
+
+        let nested: Vec<&NestedMeta> = meta.nested.iter().collect();
+        match nested.as_slice() {
+            [NestedMeta::Meta(Meta::Path(path))] if path.is_ident("transparent") => {
+                if let Err(err) = inner_field_type(&fields) {
+                    errors.push(err);
+                }
+                if let Some(kind_ty) = &kind_ty {
+                    kind_variants.push((ident, kind_ty.clone(), None, None));
+                }
+            }
+            [NestedMeta::Meta(Meta::Path(path)), NestedMeta::Meta(Meta::Path(from_flag))]
+                if path.is_ident("transparent") && from_flag.is_ident("from") =>
+            {
+                match inner_field_type(&fields) {
+                    Ok(ty) => from_variants.push((ident.clone(), ty)),
+                    Err(err) => errors.push(err),
+                }
+                if let Some(kind_ty) = &kind_ty {
+                    kind_variants.push((ident, kind_ty.clone(), None, None));
+                }
+            }
+            [NestedMeta::Meta(Meta::Path(enum_ty)), NestedMeta::Meta(Meta::Path(variant))] => {
+                kind_variants.push((ident, enum_ty.clone(), Some(variant.clone()), None));
+            }
+            [NestedMeta::Meta(Meta::Path(enum_ty)), NestedMeta::Meta(Meta::Path(variant)), NestedMeta::Lit(Lit::Str(description))] =>
+            {
+                kind_variants.push((
+                    ident,
+                    enum_ty.clone(),
+                    Some(variant.clone()),
+                    Some(description.clone()),
+                ));
+            }
+            [NestedMeta::Meta(Meta::Path(_)), NestedMeta::Meta(Meta::Path(_)), bad] => errors
+                .push(syn::Error::new_spanned(
+                    bad,
+                    "error_kind description must be a string literal",
+                )),
+            _ => errors.push(syn::Error::new_spanned(&meta, "invalid value for error_kind")),
+        }
+    }
+
+    if kind_variants.is_empty() && errors.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            &name,
+            "enum must have at least one variant",
+        ));
+    }
+
+    if !errors.is_empty() {
+        return combine_errors(errors).to_compile_error().into();
+    }
+
+    let from_impls = from_variants.into_iter().map(|(ident, inner_ty)| {
+        quote! {
+            impl From<#inner_ty> for #name {
+                fn from(value: #inner_ty) -> Self {
+                    Self::#ident(value)
+                }
+            }
+        }
+    });
+
+    let kind_enum = kind_variants
+        .first()
+        .expect("kind_variants checked non-empty above")
+        .1
+        .clone();
+    let match_arms = kind_variants.iter().map(|(ident, enum_ty, variant, _)| {
+        let fields = &variants.iter().find(|v| v.ident == *ident).unwrap().fields;
+        match fields {
+            syn::Fields::Unit => {
+                quote! {
+                    Self::#ident => #enum_ty::#variant,
+                }
+            }
+            syn::Fields::Named(_) => {
+                quote! {
+                    Self::#ident{..} => #enum_ty::#variant,
+                }
+            }
+            syn::Fields::Unnamed(_) => match variant {
+                Some(v) => quote! {
+                    Self::#ident(..) => #enum_ty::#v,
+                },
+                None => quote! {
+                    Self::#ident(inner) => ::derive_error_kind::HasKind::<#enum_ty>::kind(inner),
+                },
+            },
+        }
+    });
+
+    let chain_arms = kind_variants.iter().map(|(ident, enum_ty, variant, _)| {
+        let fields = &variants.iter().find(|v| v.ident == *ident).unwrap().fields;
+        match fields {
+            syn::Fields::Unit => {
+                quote! {
+                    Self::#ident => vec![#enum_ty::#variant],
+                }
+            }
+            syn::Fields::Named(_) => {
+                quote! {
+                    Self::#ident{..} => vec![#enum_ty::#variant],
+                }
+            }
+            syn::Fields::Unnamed(_) => match variant {
+                Some(v) => quote! {
+                    Self::#ident(..) => vec![#enum_ty::#v],
+                },
+                None => quote! {
+                    Self::#ident(inner) => inner.kind_chain(),
+                },
+            },
+        }
+    });
+
+    let describe_arms: Vec<_> = kind_variants
+        .iter()
+        .map(|(ident, _, variant, description)| {
+            let fields = &variants.iter().find(|v| v.ident == *ident).unwrap().fields;
+            let literal = describe_literal(ident, variant, description, &mut errors);
+            match fields {
+                syn::Fields::Unit => quote! {
+                    Self::#ident => #literal,
+                },
+                syn::Fields::Named(_) => quote! {
+                    Self::#ident{..} => #literal,
+                },
+                syn::Fields::Unnamed(_) => match variant {
+                    Some(_) => quote! {
+                        Self::#ident(..) => #literal,
+                    },
+                    None => quote! {
+                        Self::#ident(inner) => inner.describe(),
+                    },
+                },
+            }
+        })
+        .collect();
+
+    if !errors.is_empty() {
+        return combine_errors(errors).to_compile_error().into();
+    }
+
+    let expanded = quote! {
+        impl ::derive_error_kind::HasKind<#kind_enum> for #name {
+            fn kind(&self) -> #kind_enum {
+                match self {
+                    #(#match_arms)*
+                }
+            }
+        }
+
+        impl #name {
+            /// Thin forwarder to the `HasKind` impl, kept for backward compatibility.
+            pub fn kind(&self) -> #kind_enum {
+                ::derive_error_kind::HasKind::<#kind_enum>::kind(self)
+            }
+
+            /// Follow transparent delegations; a transparent level adds no entry, so this holds the innermost kind.
+            pub fn kind_chain(&self) -> Vec<#kind_enum> {
+                match self {
+                    #(#chain_arms)*
+                }
+            }
+
+            /// Human-readable label for this error's kind, for logging/metrics without a full `Display` impl.
+            pub fn describe(&self) -> &'static str {
+                match self {
+                    #(#describe_arms)*
+                }
+            }
+        }
+
+        #(#from_impls)*
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Resolve the literal `.describe()` returns for a non-transparent variant: the provided
+/// description, or else the stringified kind variant, without ever panicking on malformed input
+/// (e.g. a multi-segment path like `some::Variant`, which has no single identifier to stringify).
+fn describe_literal(
+    ident: &syn::Ident,
+    variant: &Option<Path>,
+    description: &Option<syn::LitStr>,
+    errors: &mut Vec<syn::Error>,
+) -> String {
+    if let Some(lit) = description {
+        return lit.value();
+    }
+    match variant {
+        Some(path) => match path.get_ident() {
+            Some(variant_ident) => variant_ident.to_string(),
+            None => {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    "error_kind variant must be a single identifier to use as a default description; add an explicit description literal instead",
+                ));
+                String::new()
+            }
+        },
+        None => ident.to_string(),
+    }
+}
+
+/// Extract the inner type of a single-field tuple variant/struct, for use
+/// by `#[error_kind(transparent)]` and `#[error_kind(transparent, from)]`.
+fn inner_field_type(fields: &syn::Fields) -> Result<syn::Type, syn::Error> {
+    match fields {
+        syn::Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            Ok(unnamed.unnamed[0].ty.clone())
+        }
+        _ => Err(syn::Error::new_spanned(
+            fields,
+            "#[error_kind(transparent)] requires a single-field tuple variant",
+        )),
+    }
+}
+
+/// Create a `kind` method for a struct that either delegates to an inner
+/// error field (`#[error_kind(transparent)]`, or a single-field tuple
+/// struct) or maps the whole struct to a fixed variant via the top-level
+/// `#[error_kind(KindEnum, Variant)]` attribute.
+fn derive_struct(input: DeriveInput) -> TokenStream {
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let name = input.ident.clone();
+
+    let top_metas = match find_attribute(&input, "error_kind") {
+        Some(metas) => metas,
+        None => {
+            errors.push(syn::Error::new_spanned(
+                &name,
+                "#[derive(ErrorKind)] requires error_kind attribute",
+            ));
+            return combine_errors(errors).to_compile_error().into();
+        }
+    };
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => data.fields.clone(),
+        _ => unreachable!("derive_struct is only called for struct input"),
+    };
+
+    let expanded = if top_metas.len() == 2 {
+        match (&top_metas[0], &top_metas[1]) {
+            (
+                NestedMeta::Meta(Meta::Path(enum_ty)),
+                NestedMeta::Meta(Meta::Path(variant)),
+            ) => {
+                let literal = describe_literal(&name, &Some(variant.clone()), &None, &mut errors);
+                quote! {
+                    impl ::derive_error_kind::HasKind<#enum_ty> for #name {
+                        fn kind(&self) -> #enum_ty {
+                            #enum_ty::#variant
+                        }
+                    }
+
+                    impl #name {
+                        /// Thin forwarder to the `HasKind` impl, kept for backward compatibility.
+                        pub fn kind(&self) -> #enum_ty {
+                            ::derive_error_kind::HasKind::<#enum_ty>::kind(self)
+                        }
+
+                        /// Kind chain of this error: its single fixed kind.
+                        pub fn kind_chain(&self) -> Vec<#enum_ty> {
+                            vec![#enum_ty::#variant]
+                        }
+
+                        /// Human-readable label for this error's kind, for logging/metrics without a full `Display` impl.
+                        pub fn describe(&self) -> &'static str {
+                            #literal
+                        }
+                    }
+                }
+            }
+            _ => {
+                errors.push(syn::Error::new_spanned(
+                    &top_metas,
+                    "invalid value for error_kind",
+                ));
+                quote! {}
+            }
+        }
+    } else if top_metas.len() == 1 {
+        match &top_metas[0] {
+            NestedMeta::Meta(Meta::Path(kind_ty)) => {
+                match transparent_field_access(&fields) {
+                    Ok(access) => quote! {
+                        impl ::derive_error_kind::HasKind<#kind_ty> for #name {
+                            fn kind(&self) -> #kind_ty {
+                                ::derive_error_kind::HasKind::<#kind_ty>::kind(&self.#access)
+                            }
+                        }
+
+                        impl #name {
+                            /// Thin forwarder to the `HasKind` impl, kept for backward compatibility.
+                            pub fn kind(&self) -> #kind_ty {
+                                ::derive_error_kind::HasKind::<#kind_ty>::kind(self)
+                            }
+
+                            /// Follow transparent delegations; a transparent level adds no entry, so this holds the innermost kind.
+                            pub fn kind_chain(&self) -> Vec<#kind_ty> {
+                                self.#access.kind_chain()
+                            }
+
+                            /// Human-readable label for this error's kind, for logging/metrics without a full `Display` impl.
+                            pub fn describe(&self) -> &'static str {
+                                self.#access.describe()
+                            }
+                        }
+                    },
+                    Err(err) => {
+                        errors.push(err);
+                        quote! {}
+                    }
+                }
+            }
+            _ => {
+                errors.push(syn::Error::new_spanned(
+                    &top_metas,
+                    "#[error_kind(KIND_IDENT)] attribute requires an identifier",
+                ));
+                quote! {}
+            }
+        }
+    } else {
+        errors.push(syn::Error::new_spanned(
+            &top_metas,
+            "error_kind must have one or two arguments",
+        ));
+        quote! {}
+    };
+
+    if !errors.is_empty() {
+        return combine_errors(errors).to_compile_error().into();
+    }
+
+    TokenStream::from(expanded)
+}
+
+/// Find the struct field that should be used to delegate `.kind()` to: the
+/// one explicitly marked `#[error_kind(transparent)]`, or, failing that, the
+/// single field of a newtype tuple struct.
+fn transparent_field_access(fields: &syn::Fields) -> Result<proc_macro2::TokenStream, syn::Error> {
+    for (index, field) in fields.iter().enumerate() {
+        let is_transparent = field.attrs.iter().any(|attr| {
+            attr.path.is_ident("error_kind")
+                && matches!(
+                    attr.parse_meta(),
+                    Ok(Meta::List(meta)) if meta.nested.len() == 1
+                        && matches!(&meta.nested[0], NestedMeta::Meta(Meta::Path(p)) if p.is_ident("transparent"))
+                )
+        });
+        if is_transparent {
+            return Ok(match &field.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let index = syn::Index::from(index);
+                    quote! { #index }
+                }
+            });
+        }
+    }
+
+    if let syn::Fields::Unnamed(unnamed) = fields {
+        if unnamed.unnamed.len() == 1 {
+            let index = syn::Index::from(0);
+            return Ok(quote! { #index });
+        }
+    }
+
+    Err(syn::Error::new_spanned(
+        fields,
+        "struct must have a field marked `#[error_kind(transparent)]`, or be a single-field tuple struct",
+    ))
+}
+
+/// Fold a non-empty list of parsing errors into a single `syn::Error`,
+/// so every malformed variant is reported in the same compilation pass.
+fn combine_errors(errors: Vec<syn::Error>) -> syn::Error {
+    let mut iter = errors.into_iter();
+    let mut combined = iter.next().expect("combine_errors requires at least one error");
+    for err in iter {
+        combined.combine(err);
+    }
+    combined
+}
+
+fn get_kind_ty(input: &DeriveInput) -> Result<Path, syn::Error> {
+    let metas = find_attribute(input, "error_kind").ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(ErrorKind)] requires error_kind attribute",
+        )
+    })?;
+    if let Some(&NestedMeta::Meta(Meta::Path(ref path))) = metas.iter().next() {
+        Ok(path.to_owned())
+    } else {
+        Err(syn::Error::new_spanned(
+            &metas,
+            "#[error_kind(KIND_IDENT)] attribute requires an identifier",
+        ))
+    }
+}
+
+/// Get an attribute from the input.
+///
+/// Adapted from https://crates.io/crates/enum-kinds
+fn find_attribute(
+    definition: &DeriveInput,
+    name: &str,
+) -> Option<Punctuated<NestedMeta, syn::token::Comma>> {
+    for attr in definition.attrs.iter() {
+        match attr.parse_meta() {
+            Ok(Meta::List(MetaList {
+                ref path,
+                ref nested,
+                ..
+            })) if path.is_ident(name) => return Some(nested.clone()),
+            _ => continue,
+        }
+    }
+    None
+}
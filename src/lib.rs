@@ -42,6 +42,13 @@
 //! ```rust
 //! use derive_error_kind::ErrorKind;
 //!
+//! #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+//! pub enum ErrorKind {
+//!     NotFound,
+//!     InvalidInput,
+//!     InternalError,
+//! }
+//!
 //! #[derive(Debug, ErrorKind)]
 //! #[error_kind(ErrorKind)]
 //! pub enum MyError {
@@ -60,183 +67,84 @@
 //! assert_eq!(error.kind(), ErrorKind::NotFound);
 //! ```
 //!
+//! ## Struct Support
+//!
+//! The macro can also be derived on structs, covering the common `struct Error { inner: ... }`
+//! wrapper pattern. A field marked `#[error_kind(transparent)]` (or the sole field of a newtype
+//! tuple struct) delegates `.kind()` to it; a top-level `#[error_kind(KindEnum, Variant)]`
+//! attribute instead maps the whole struct to one fixed variant:
+//!
+//! ```rust
+//! use derive_error_kind::ErrorKind;
+//!
+//! #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+//! pub enum ErrorKind {
+//!     NotFound,
+//!     InternalError,
+//! }
+//!
+//! #[derive(Debug, ErrorKind)]
+//! #[error_kind(ErrorKind, InternalError)]
+//! pub struct ConfigError {
+//!     message: String,
+//! }
+//!
+//! #[derive(Debug, ErrorKind)]
+//! #[error_kind(ErrorKind, NotFound)]
+//! pub struct NotFoundError;
+//!
+//! #[derive(Debug, ErrorKind)]
+//! #[error_kind(ErrorKind)]
+//! pub struct WrapperError {
+//!     #[error_kind(transparent)]
+//!     inner: ConfigError,
+//! }
+//!
+//! let config_error = ConfigError { message: "bad config".to_string() };
+//! assert_eq!(config_error.kind(), ErrorKind::InternalError);
+//! assert_eq!(NotFoundError.kind(), ErrorKind::NotFound);
+//!
+//! let wrapper = WrapperError { inner: config_error };
+//! assert_eq!(wrapper.kind(), ErrorKind::InternalError);
+//!
+//! // kind_chain() and describe() are generated for struct derivations too, just like for enums.
+//! assert_eq!(NotFoundError.kind_chain(), vec![ErrorKind::NotFound]);
+//! assert_eq!(wrapper.kind_chain(), vec![ErrorKind::InternalError]);
+//!
+//! assert_eq!(NotFoundError.describe(), "NotFound");
+//! assert_eq!(wrapper.describe(), "InternalError");
+//! ```
+//!
 //! ## Attribute Reference
 //!
 //! - `#[error_kind(KindEnum)]`: Top-level attribute that specifies which enum to use for error kinds
 //! - `#[error_kind(KindEnum, Variant)]`: Variant-level attribute that specifies which variant of the kind enum to return
+//! - `#[error_kind(KindEnum, Variant, "description")]`: Same as above, plus a literal returned by the generated `.describe()` method; without it, `.describe()` falls back to the stringified variant name
 //! - `#[error_kind(transparent)]`: Variant-level attribute for nested errors, indicating that the inner error's kind should be used
+//! - `#[error_kind(transparent, from)]`: Same as above, plus a generated `From<Inner>` impl for the
+//!   variant, so the inner error can be propagated with `?` (the variant must be a single-field
+//!   tuple variant)
+//!
+//! Alongside `.kind()`, every derived type also gets a `.kind_chain()` method that returns a
+//! `Vec` of kinds along its transparent nesting path. A transparent level adds no entry of its
+//! own, so the `Vec` holds one entry: the kind of the innermost non-transparent variant. Inner
+//! types reached through a `transparent` variant must also derive `ErrorKind` so that
+//! `.kind_chain()` is available on them.
+//!
+//! The derive implements [`HasKind<K>`] rather than a bare inherent method, so transparent
+//! delegation resolves uniformly even when the inner error lives in another crate. The inherent
+//! `.kind()` method is still generated as a thin forwarder, so existing call sites are unaffected.
 //!
 //! ## Requirements
 //!
-//! - The macro can only be applied to enums
-//! - Each variant must have an `error_kind` attribute
+//! - The macro can be applied to enums and to structs
+//! - Each enum variant must have an `error_kind` attribute
 //! - The kind enum must be in scope and accessible
 
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{
-    parse_macro_input, punctuated::Punctuated, DeriveInput, Meta, MetaList, NestedMeta, Path,
-};
-
-/// Create a kind method for struct
-/// # Examples
-/// ```
-/// use derive_error_kind::ErrorKind;
-///#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-/// enum ErrorType {
-///     A,
-///     B,
-///     C,
-/// }
-///
-/// #[derive(ErrorKind)]
-/// #[error_kind(ErrorType)]
-/// enum CacheError {
-///     #[error_kind(ErrorType, A)]
-///     Poisoned,
-///
-///     #[error_kind(ErrorType, B)]
-///     Missing,
-/// }
-///
-/// #[derive(ErrorKind)]
-/// #[error_kind(ErrorType)]
-/// enum ServiceError {
-///     #[error_kind(transparent)]
-///     Cache(CacheError),
-///
-///     #[error_kind(ErrorType, C)]
-///     Db,
-/// }
-///
-/// assert_eq!(ServiceError::Cache(CacheError::Missing).kind(), ErrorType::B);
-/// assert_eq!(ServiceError::Db.kind(), ErrorType::C);
-/// ```
-#[proc_macro_derive(ErrorKind, attributes(error_kind))]
-pub fn error_kind(input: TokenStream) -> TokenStream {
-    error_kind_macro(input)
-}
-
-fn error_kind_macro(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let kind_ty = get_kind_ty(&input);
-
-    let name = input.ident;
-    let variants = if let syn::Data::Enum(data) = input.data {
-        data.variants
-    } else {
-        panic!("ImplKind just can be used in enums");
-    };
-
-    let mut kind_variants = Vec::new();
-
-    for variant in variants.clone() {
-        let ident = variant.ident;
-        if let Some(attr) = variant
-            .attrs
-            .into_iter()
-            .find(|attr| attr.path.is_ident("error_kind"))
-        {
-            if let Ok(syn::Meta::List(meta)) = attr.parse_meta() {
-                if meta.nested.len() == 2 {
-                    if let (
-                        syn::NestedMeta::Meta(syn::Meta::Path(enum_ty)),
-                        syn::NestedMeta::Meta(syn::Meta::Path(variant)),
-                    ) = (&meta.nested[0], &meta.nested[1])
-                    {
-                        kind_variants.push((ident, enum_ty.clone(), Some(variant.clone())));
-                    } else {
-                        panic!("Invalid value for error_kind");
-                    }
-                } else if meta.nested.len() == 1 {
-                    for sub_meta in meta.nested {
-                        if let NestedMeta::Meta(Meta::Path(path)) = sub_meta {
-                            if path.is_ident("transparent") {
-                                kind_variants.push((ident.clone(), kind_ty.clone(), None));
-                            }
-                        } else {
-                            panic!("Invalid value for #[error_kind]");
-                        }
-                    }
-                } else {
-                    panic!("error_kind must have one two arguments");
-                }
-            } else {
-                panic!("Error parsing meta");
-            }
-        } else {
-            panic!("Enum variants must have the attribute `error_kind`");
-        }
-    }
-
-    let kind_enum = kind_variants
-        .first()
-        .expect("No variants in Enum")
-        .1
-        .clone();
-    let match_arms = kind_variants.into_iter().map(|(ident, enum_ty, variant)| {
-        let fields = &variants.iter().find(|v| v.ident == ident).unwrap().fields;
-        match fields {
-            syn::Fields::Unit => {
-                quote! {
-                    Self::#ident => #enum_ty::#variant,
-                }
-            }
-            syn::Fields::Named(_) => {
-                quote! {
-                    Self::#ident{..} => #enum_ty::#variant,
-                }
-            }
-            syn::Fields::Unnamed(_) => match variant {
-                Some(v) => quote! {
-                    Self::#ident(..) => #enum_ty::#v,
-                },
-                None => quote! {
-                    Self::#ident(inner) => inner.kind(),
-                },
-            },
-        }
-    });
-
-    let expanded = quote! {
-        impl #name {
-            pub fn kind(&self) -> #kind_enum {
-                match self {
-                    #(#match_arms)*
-                }
-            }
-        }
-    };
-
-    TokenStream::from(expanded)
-}
-
-fn get_kind_ty(input: &DeriveInput) -> Path {
-    let metas = find_attribute(input, "error_kind")
-        .expect("#[derive(ErrorKind)] requires error_kind attribute");
-    if let Some(&NestedMeta::Meta(Meta::Path(ref path))) = metas.iter().next() {
-        path.to_owned()
-    } else {
-        panic!("#[error_kind(KIND_IDENT)] attribute requires and identifier");
-    }
-}
+pub use derive_error_kind_derive::ErrorKind;
 
-/// Get an attribute from the input.
-/// 
-/// Adapted from https://crates.io/crates/enum-kinds
-fn find_attribute(
-    definition: &DeriveInput,
-    name: &str,
-) -> Option<Punctuated<NestedMeta, syn::token::Comma>> {
-    for attr in definition.attrs.iter() {
-        match attr.parse_meta() {
-            Ok(Meta::List(MetaList {
-                ref path,
-                ref nested,
-                ..
-            })) if path.is_ident(name) => return Some(nested.clone()),
-            _ => continue,
-        }
-    }
-    None
+/// Uniform access to a type's kind, implemented by the derive macro instead of a bare inherent
+/// method so that transparent delegation resolves across crate boundaries.
+pub trait HasKind<K> {
+    fn kind(&self) -> K;
 }